@@ -3,11 +3,31 @@
 //!
 //! See <https://arxiv.org/html/0901.4016> for an introduction and the
 //! specification.
+//!
+//! This crate is `no_std`. The core encoding/decoding functions work on
+//! caller-provided buffers and need no allocator. The `alloc` feature adds
+//! [`proquints`] and a [`FromProquint`] impl for `Vec<u8>`; the `std` feature
+//! (enabled by default) adds the `Ipv4Addr`/`Ipv6Addr` impls.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-use std::convert::AsRef;
-use std::mem::size_of;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::AsRef;
+use core::fmt;
+use core::mem::size_of;
+use core::str;
+#[cfg(feature = "std")]
 use std::net::{Ipv4Addr, Ipv6Addr};
-use std::str;
 
 static CONSONANTS: [u8; 16] = [
     b'b', b'd', b'f', b'g', b'h', b'j', b'k', b'l', b'm', b'n', b'p', b'r', b's', b't', b'v', b'z',
@@ -16,6 +36,7 @@ static CONSONANTS: [u8; 16] = [
 static VOWELS: [u8; 4] = [b'a', b'i', b'o', b'u'];
 
 /// Create the proquint for `input`.
+#[cfg(feature = "alloc")]
 pub fn proquints<T: Proquint>(input: T) -> String {
     let input = input.as_bytes();
     let input = input.as_ref();
@@ -33,7 +54,7 @@ pub fn proquints<T: Proquint>(input: T) -> String {
 /// This will panic if `input`'s length is not even or if the `buf`fer's length
 /// is not larger than [`output_length`]`(buf)`.
 pub fn proquints_buf<'a>(input: &[u8], buf: &'a mut [u8], separator: u8) -> &'a str {
-    assert!(input.len() % 2 == 0);
+    assert!(input.len().is_multiple_of(2));
     assert!(output_length(input.len()) >= buf.len());
     let mut i = 0;
     let mut c = 0;
@@ -59,6 +80,85 @@ pub fn proquints_buf<'a>(input: &[u8], buf: &'a mut [u8], separator: u8) -> &'a
     unsafe { str::from_utf8_unchecked(&buf[..i]) }
 }
 
+/// Create an iterator that lazily yields the proquint characters for
+/// `input`, separated by `separator`.
+///
+/// Unlike [`proquints`] and [`proquints_buf`] this doesn't allocate or
+/// require a pre-sized buffer, so it can be used to stream proquints
+/// directly into a [`fmt::Formatter`], a writer, or a hashing sink.
+///
+/// # Panics
+///
+/// This will panic if `input`'s length is not even, same as
+/// [`proquints_buf`].
+pub fn proquints_iter(input: &[u8], separator: u8) -> ProquintEncoder<'_> {
+    assert!(input.len().is_multiple_of(2));
+    ProquintEncoder {
+        input,
+        separator,
+        pos: 0,
+        sub: 0,
+        word: 0,
+    }
+}
+
+/// Iterator returned by [`proquints_iter`].
+///
+/// Internally this tracks the current 16-bit word together with which of the
+/// five sub-symbols (plus the separator) is next, emitting one `char` per
+/// [`next`](Iterator::next) call.
+pub struct ProquintEncoder<'a> {
+    input: &'a [u8],
+    separator: u8,
+    pos: usize,
+    // 0..=4 is the next sub-symbol to emit for `word`, 5 means "emit the
+    // separator next", 6 means "done".
+    sub: u8,
+    word: u16,
+}
+
+impl<'a> Iterator for ProquintEncoder<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        match self.sub {
+            6 => None,
+            5 => {
+                self.sub = 0;
+                Some(char::from(self.separator))
+            }
+            sub => {
+                if sub == 0 {
+                    if self.pos >= self.input.len() {
+                        return None;
+                    }
+                    self.word =
+                        (u16::from(self.input[self.pos]) << 8) | u16::from(self.input[self.pos + 1]);
+                    self.pos += 2;
+                }
+
+                let c = match sub {
+                    0 => CONSONANTS[usize::from((self.word & 0b1111_0000_0000_0000) >> 12)],
+                    1 => VOWELS[usize::from((self.word & 0b0000_1100_0000_0000) >> 10)],
+                    2 => CONSONANTS[usize::from((self.word & 0b0000_0011_1100_0000) >> 6)],
+                    3 => VOWELS[usize::from((self.word & 0b0000_0000_0011_0000) >> 4)],
+                    4 => CONSONANTS[usize::from(self.word & 0b0000_0000_0000_1111)],
+                    _ => unreachable!("invalid sub-symbol position"),
+                };
+
+                self.sub = if sub < 4 {
+                    sub + 1
+                } else if self.pos < self.input.len() {
+                    5
+                } else {
+                    6
+                };
+                Some(char::from(c))
+            }
+        }
+    }
+}
+
 /// Returns the output length for `input_length`.
 ///
 /// The returned length for `input_length`s that are not even is invalid.
@@ -68,6 +168,148 @@ pub const fn output_length(input_length: usize) -> usize {
     ((input_length / 2) * 6) - 1
 }
 
+/// Returns `true` if `s` has the structure of a proquint string: one or more
+/// five-character consonant/vowel/consonant/vowel/consonant groups, joined by
+/// a single (arbitrary, but consistent) separator byte.
+///
+/// This doesn't decode `s`, it only checks its shape, so it's cheap enough to
+/// use to sniff whether a user-supplied token is a proquint before calling
+/// [`decode`].
+pub fn is_proquint(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() || bytes.len() % 6 != 5 {
+        // Every valid length is of the form `output_length(n)` for some even
+        // `n`, i.e. `bytes.len() % 6 == 5`.
+        return false;
+    }
+
+    let mut separator = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !is_proquint_group(&bytes[i..i + 5]) {
+            return false;
+        }
+        i += 5;
+
+        if i < bytes.len() {
+            let sep = bytes[i];
+            if *separator.get_or_insert(sep) != sep {
+                return false;
+            }
+            i += 1;
+        }
+    }
+    true
+}
+
+/// Returns `true` if `s` is a single proquint group: five characters
+/// alternating consonant/vowel/consonant/vowel/consonant.
+pub fn looks_like_proquint(s: &str) -> bool {
+    is_proquint_group(s.as_bytes())
+}
+
+/// Returns `true` if `group` is five bytes alternating
+/// consonant/vowel/consonant/vowel/consonant.
+fn is_proquint_group(group: &[u8]) -> bool {
+    group.len() == 5
+        && CONSONANTS.contains(&group[0])
+        && VOWELS.contains(&group[1])
+        && CONSONANTS.contains(&group[2])
+        && VOWELS.contains(&group[3])
+        && CONSONANTS.contains(&group[4])
+}
+
+/// Decode `input` back into the bytes it was created from, writing them into
+/// `buf`.
+///
+/// `input` is read five characters at a time (a consonant/vowel/consonant/
+/// vowel/consonant group), skipping a single separator byte between groups;
+/// this is the inverse of [`proquints_buf`].
+pub fn decode<'a>(input: &str, buf: &'a mut [u8]) -> Result<&'a [u8], DecodeError> {
+    let input = input.as_bytes();
+    let mut i = 0;
+    let mut c = 0;
+    while c < input.len() {
+        if c + 5 > input.len() {
+            return Err(DecodeError::InvalidGroupLength);
+        }
+        let c0 = consonant_index(input[c])?;
+        let v0 = vowel_index(input[c + 1])?;
+        let c1 = consonant_index(input[c + 2])?;
+        let v1 = vowel_index(input[c + 3])?;
+        let c2 = consonant_index(input[c + 4])?;
+        c += 5;
+
+        if i + 2 > buf.len() {
+            return Err(DecodeError::BufferTooSmall);
+        }
+        let b = (c0 << 12) | (v0 << 10) | (c1 << 6) | (v1 << 4) | c2;
+        let [b0, b1] = b.to_be_bytes();
+        buf[i] = b0;
+        buf[i + 1] = b1;
+        i += 2;
+
+        if c < input.len() {
+            // Skip the separator between groups.
+            c += 1;
+        }
+    }
+    Ok(&buf[..i])
+}
+
+/// Returns the index of `byte` in [`CONSONANTS`], or an error if it's not a
+/// valid consonant.
+fn consonant_index(byte: u8) -> Result<u16, DecodeError> {
+    CONSONANTS
+        .iter()
+        .position(|&b| b == byte)
+        .map(|i| i as u16)
+        .ok_or(DecodeError::InvalidCharacter(byte))
+}
+
+/// Returns the index of `byte` in [`VOWELS`], or an error if it's not a valid
+/// vowel.
+fn vowel_index(byte: u8) -> Result<u16, DecodeError> {
+    VOWELS
+        .iter()
+        .position(|&b| b == byte)
+        .map(|i| i as u16)
+        .ok_or(DecodeError::InvalidCharacter(byte))
+}
+
+/// Error returned by [`decode`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// `input` contains a byte that's not a valid consonant or vowel in the
+    /// position it was found.
+    InvalidCharacter(u8),
+    /// A group didn't contain exactly five characters.
+    InvalidGroupLength,
+    /// `buf` is too small to hold the decoded bytes.
+    BufferTooSmall,
+    /// The decoded bytes don't fill the output exactly, used by
+    /// [`from_proquint`] when `s` decodes to fewer bytes than `T` requires.
+    LengthMismatch,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter(byte) => {
+                write!(f, "invalid proquint character: '{}'", *byte as char)
+            }
+            DecodeError::InvalidGroupLength => {
+                write!(f, "proquint group is not exactly five characters")
+            }
+            DecodeError::BufferTooSmall => write!(f, "buffer too small to decode into"),
+            DecodeError::LengthMismatch => write!(f, "decoded length doesn't match expected type"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
 /// Trait to define what types can be used in [`proquints`].
 ///
 /// Note that it's not required to implement this trait. You can also convert
@@ -125,6 +367,7 @@ impl Proquint for usize {
     }
 }
 
+#[cfg(feature = "std")]
 impl Proquint for Ipv4Addr {
     type Bytes = [u8; 4];
 
@@ -133,6 +376,7 @@ impl Proquint for Ipv4Addr {
     }
 }
 
+#[cfg(feature = "std")]
 impl Proquint for Ipv6Addr {
     type Bytes = [u8; 16];
 
@@ -140,3 +384,94 @@ impl Proquint for Ipv6Addr {
         self.octets()
     }
 }
+
+/// Create `T` from the proquint `s`.
+pub fn from_proquint<T: FromProquint>(s: &str) -> Result<T, DecodeError> {
+    T::from_proquint(s)
+}
+
+/// Trait to define what types can be used in [`from_proquint`], the inverse
+/// of [`Proquint`].
+pub trait FromProquint: Sized {
+    /// Create `Self` from the proquint `s`.
+    fn from_proquint(s: &str) -> Result<Self, DecodeError>;
+}
+
+impl FromProquint for u16 {
+    fn from_proquint(s: &str) -> Result<Self, DecodeError> {
+        let mut buf = [0u8; size_of::<u16>()];
+        decode_exact(s, &mut buf)?;
+        Ok(u16::from_be_bytes(buf))
+    }
+}
+
+impl FromProquint for u32 {
+    fn from_proquint(s: &str) -> Result<Self, DecodeError> {
+        let mut buf = [0u8; size_of::<u32>()];
+        decode_exact(s, &mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+}
+
+impl FromProquint for u64 {
+    fn from_proquint(s: &str) -> Result<Self, DecodeError> {
+        let mut buf = [0u8; size_of::<u64>()];
+        decode_exact(s, &mut buf)?;
+        Ok(u64::from_be_bytes(buf))
+    }
+}
+
+impl FromProquint for usize {
+    fn from_proquint(s: &str) -> Result<Self, DecodeError> {
+        let mut buf = [0u8; size_of::<usize>()];
+        decode_exact(s, &mut buf)?;
+        Ok(usize::from_be_bytes(buf))
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromProquint for Ipv4Addr {
+    fn from_proquint(s: &str) -> Result<Self, DecodeError> {
+        let mut buf = [0u8; 4];
+        decode_exact(s, &mut buf)?;
+        Ok(Ipv4Addr::from(buf))
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromProquint for Ipv6Addr {
+    fn from_proquint(s: &str) -> Result<Self, DecodeError> {
+        let mut buf = [0u8; 16];
+        decode_exact(s, &mut buf)?;
+        Ok(Ipv6Addr::from(buf))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromProquint for Vec<u8> {
+    fn from_proquint(s: &str) -> Result<Self, DecodeError> {
+        // `decoded_length` over-estimates when `s` is malformed, `decode`
+        // below catches that.
+        let mut buf = vec![0u8; decoded_length(s.len())];
+        let len = decode(s, &mut buf)?.len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+}
+
+/// Decode `s` into `buf`, returning an error if the decoded bytes don't fill
+/// `buf` exactly.
+fn decode_exact(s: &str, buf: &mut [u8]) -> Result<(), DecodeError> {
+    let len = decode(s, buf)?.len();
+    if len != buf.len() {
+        return Err(DecodeError::LengthMismatch);
+    }
+    Ok(())
+}
+
+/// Returns the maximum number of decoded bytes for a proquint string of
+/// `input_length`, the (over-estimating) inverse of [`output_length`].
+#[cfg(feature = "alloc")]
+const fn decoded_length(input_length: usize) -> usize {
+    ((input_length + 1) / 6) * 2
+}
@@ -1,4 +1,9 @@
-use proquints::proquints;
+use std::net::Ipv4Addr;
+
+use proquints::{
+    decode, from_proquint, is_proquint, looks_like_proquint, proquints, proquints_iter,
+    DecodeError,
+};
 
 #[test]
 fn simple() {
@@ -23,3 +28,76 @@ fn simple() {
         assert_eq!(got, expected, "input: {input:?}");
     }
 }
+
+#[test]
+fn decode_roundtrip() {
+    // Same fixtures as `simple`, decoded back into bytes.
+    let tests = [
+        ([127, 0, 0, 1], "lusab-babad"),
+        ([63, 84, 220, 193], "gutih-tugad"),
+        ([147, 67, 119, 2], "natag-lisaf"),
+        ([12, 110, 110, 204], "budov-kuras"),
+    ];
+
+    for (expected, input) in tests {
+        let mut buf = [0u8; 4];
+        let got = decode(input, &mut buf).unwrap();
+        assert_eq!(got, expected, "input: {input:?}");
+    }
+}
+
+#[test]
+fn decode_errors() {
+    let mut buf = [0u8; 4];
+    assert_eq!(
+        decode("lusac-babad", &mut buf),
+        Err(DecodeError::InvalidCharacter(b'c'))
+    );
+    assert_eq!(
+        decode("lusab-bab", &mut buf),
+        Err(DecodeError::InvalidGroupLength)
+    );
+    let mut small_buf = [0u8; 1];
+    assert_eq!(
+        decode("lusab-babad", &mut small_buf),
+        Err(DecodeError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn from_proquint_typed() {
+    let ip: Ipv4Addr = from_proquint("lusab-babad").unwrap();
+    assert_eq!(ip, Ipv4Addr::new(127, 0, 0, 1));
+
+    let n: u32 = from_proquint("lusab-babad").unwrap();
+    assert_eq!(n, u32::from_be_bytes([127, 0, 0, 1]));
+
+    let bytes: Vec<u8> = from_proquint("lusab-babad").unwrap();
+    assert_eq!(bytes, vec![127, 0, 0, 1]);
+}
+
+#[test]
+fn is_proquint_validation() {
+    assert!(is_proquint("lusab-babad"));
+    assert!(is_proquint("lusab babad")); // any separator byte is accepted.
+    assert!(is_proquint("lusab"));
+    assert!(looks_like_proquint("lusab"));
+
+    assert!(!is_proquint("")); // empty.
+    assert!(!is_proquint("lusac-babad")); // 'c' isn't a consonant.
+    assert!(!is_proquint("lusab-bab")); // last group too short.
+    assert!(!is_proquint("lusab-babad-")); // trailing separator.
+    assert!(!is_proquint("lusab babad-wanog")); // inconsistent separator.
+    assert!(!looks_like_proquint("lusa"));
+}
+
+#[test]
+fn proquints_iter_matches_proquints_buf() {
+    let tests: [&[u8]; 3] = [&[127, 0, 0, 1], &[63, 84, 220, 193], &[12, 110, 110, 204]];
+
+    for input in tests {
+        let expected = proquints(input);
+        let got: String = proquints_iter(input, b'-').collect();
+        assert_eq!(got, expected, "input: {input:?}");
+    }
+}